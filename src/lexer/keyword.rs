@@ -0,0 +1,137 @@
+use std::fmt;
+
+/// Reserved words that lex as `TokenType::Keyword` rather than
+/// `TokenType::Identifier`, checked by exact match against the accumulated
+/// identifier text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    Break,
+    Case,
+    Catch,
+    Class,
+    Const,
+    Continue,
+    Debugger,
+    Default,
+    Delete,
+    Do,
+    Else,
+    Export,
+    Extends,
+    False,
+    Finally,
+    For,
+    Function,
+    If,
+    Import,
+    In,
+    InstanceOf,
+    New,
+    Null,
+    Return,
+    Super,
+    Switch,
+    This,
+    Throw,
+    True,
+    Try,
+    TypeOf,
+    Var,
+    Let,
+    Void,
+    While,
+    With,
+    Yield,
+}
+
+impl Keyword {
+    /// Classifies `s` as a reserved word, or `None` if it should lex as a
+    /// plain identifier.
+    pub fn from_str(s: &str) -> Option<Keyword> {
+        match s {
+            "break" => Some(Keyword::Break),
+            "case" => Some(Keyword::Case),
+            "catch" => Some(Keyword::Catch),
+            "class" => Some(Keyword::Class),
+            "const" => Some(Keyword::Const),
+            "continue" => Some(Keyword::Continue),
+            "debugger" => Some(Keyword::Debugger),
+            "default" => Some(Keyword::Default),
+            "delete" => Some(Keyword::Delete),
+            "do" => Some(Keyword::Do),
+            "else" => Some(Keyword::Else),
+            "export" => Some(Keyword::Export),
+            "extends" => Some(Keyword::Extends),
+            "false" => Some(Keyword::False),
+            "finally" => Some(Keyword::Finally),
+            "for" => Some(Keyword::For),
+            "function" => Some(Keyword::Function),
+            "if" => Some(Keyword::If),
+            "import" => Some(Keyword::Import),
+            "in" => Some(Keyword::In),
+            "instanceof" => Some(Keyword::InstanceOf),
+            "new" => Some(Keyword::New),
+            "null" => Some(Keyword::Null),
+            "return" => Some(Keyword::Return),
+            "super" => Some(Keyword::Super),
+            "switch" => Some(Keyword::Switch),
+            "this" => Some(Keyword::This),
+            "throw" => Some(Keyword::Throw),
+            "true" => Some(Keyword::True),
+            "try" => Some(Keyword::Try),
+            "typeof" => Some(Keyword::TypeOf),
+            "var" => Some(Keyword::Var),
+            "let" => Some(Keyword::Let),
+            "void" => Some(Keyword::Void),
+            "while" => Some(Keyword::While),
+            "with" => Some(Keyword::With),
+            "yield" => Some(Keyword::Yield),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Keyword::Break => "break",
+            Keyword::Case => "case",
+            Keyword::Catch => "catch",
+            Keyword::Class => "class",
+            Keyword::Const => "const",
+            Keyword::Continue => "continue",
+            Keyword::Debugger => "debugger",
+            Keyword::Default => "default",
+            Keyword::Delete => "delete",
+            Keyword::Do => "do",
+            Keyword::Else => "else",
+            Keyword::Export => "export",
+            Keyword::Extends => "extends",
+            Keyword::False => "false",
+            Keyword::Finally => "finally",
+            Keyword::For => "for",
+            Keyword::Function => "function",
+            Keyword::If => "if",
+            Keyword::Import => "import",
+            Keyword::In => "in",
+            Keyword::InstanceOf => "instanceof",
+            Keyword::New => "new",
+            Keyword::Null => "null",
+            Keyword::Return => "return",
+            Keyword::Super => "super",
+            Keyword::Switch => "switch",
+            Keyword::This => "this",
+            Keyword::Throw => "throw",
+            Keyword::True => "true",
+            Keyword::Try => "try",
+            Keyword::TypeOf => "typeof",
+            Keyword::Var => "var",
+            Keyword::Let => "let",
+            Keyword::Void => "void",
+            Keyword::While => "while",
+            Keyword::With => "with",
+            Keyword::Yield => "yield",
+        };
+        write!(f, "{}", s)
+    }
+}
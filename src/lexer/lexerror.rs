@@ -0,0 +1,22 @@
+use std::fmt;
+use super::location::Location;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexError {
+    UnexpectedCharacter(Location, char),
+    UnexpectedEndOfInput(Location),
+    InvalidCodePoint(Location, u32),
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LexError::UnexpectedCharacter(loc, c) =>
+                write!(f, "unexpected character '{}' at {}:{}", c, loc.line, loc.column),
+            LexError::UnexpectedEndOfInput(loc) =>
+                write!(f, "unexpected end of input at {}:{}", loc.line, loc.column),
+            LexError::InvalidCodePoint(loc, code) =>
+                write!(f, "invalid code point U+{:X} at {}:{}", code, loc.line, loc.column),
+        }
+    }
+}
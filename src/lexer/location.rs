@@ -0,0 +1,13 @@
+/// A 1-based line/column position in the source, used to report errors at
+/// a human-readable location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Location {
+    pub fn new(line: u32, column: u32) -> Location {
+        Location { line, column }
+    }
+}
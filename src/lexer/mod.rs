@@ -1,11 +1,13 @@
 use std::iter::Peekable;
 
+mod keyword;
 mod lexerror;
 mod location;
 mod token;
 
 use self::location::Location;
-use self::token::{CommentStyle,QuoteStyle,Token,TokenType};
+use self::keyword::Keyword;
+use self::token::{CommentStyle,NumberLiteral,QuoteStyle,Radix,Span,StringLiteral,Token,TokenType};
 use self::lexerror::LexError;
 
 pub struct Lexer<I>
@@ -14,6 +16,23 @@ where I: Iterator<Item = char>,
     column: u32,
     line: u32,
     stream: Peekable<I>,
+    // Tracks the lexer goal symbol: true while a `/` at the current position
+    // would begin an expression (so it may open a regex literal), false
+    // while it follows an operand (so it must be division). The parser
+    // toggles this between tokens via `allow_regex`, since only it knows
+    // which position it's in.
+    allow_regex: bool,
+    // One entry per template substitution we're currently inside
+    // (`` `...${ `` seen but its matching `}` not yet reached), counting the
+    // depth of plain (non-template) braces opened since that substitution
+    // started. A `}` resumes template scanning only when it closes the
+    // substitution itself, i.e. the top entry is `0`; otherwise it closes an
+    // ordinary object literal or block nested inside the substitution.
+    brace_stack: Vec<u32>,
+    // Byte offset into the original source of the next unconsumed char,
+    // tracked alongside line/column so a `Span` can slice the exact source
+    // text of a token without re-deriving offsets from line/column.
+    byte_offset: usize,
 }
 
 impl<I> Lexer<I>
@@ -23,10 +42,20 @@ where I: Iterator<Item = char>,
         Lexer {
             column: 1,
             line: 1,
-            stream: stream.peekable()
+            stream: stream.peekable(),
+            allow_regex: true,
+            brace_stack: Vec::new(),
+            byte_offset: 0,
         }
     }
 
+    /// Sets whether a `/` at the current position may begin a regex literal
+    /// (as opposed to division). The parser calls this after each token to
+    /// reflect whether it is in an expression position.
+    pub fn allow_regex(&mut self, allow: bool) {
+        self.allow_regex = allow;
+    }
+
     fn get_location(&self) -> Location {
         Location::new(self.line, self.column)
     }
@@ -36,10 +65,12 @@ where I: Iterator<Item = char>,
             Some('\n') => {
                 self.line += 1;
                 self.column = 1;
+                self.byte_offset += '\n'.len_utf8();
                 Some('\n')
             },
             Some(x) => {
                 self.column += 1;
+                self.byte_offset += x.len_utf8();
                 Some(x)
             },
             None => None,
@@ -122,7 +153,8 @@ where I: Iterator<Item = char>,
     }
 
     fn string(&mut self, quote: QuoteStyle) -> Result<TokenType, LexError> {
-        let mut s = String::new();
+        let mut cooked = String::new();
+        let mut raw = String::new();
 
         // Skip leading quote
         self.skip();
@@ -131,31 +163,300 @@ where I: Iterator<Item = char>,
             match self.next_char() {
                 Some('"')  if quote == QuoteStyle::Double => break,
                 Some('\'') if quote == QuoteStyle::Single => break,
+                Some('\\') => self.escape(&mut cooked, &mut raw)?,
+                Some(c) if is_line_terminator(c) => return Err(self.unexpected_char(c)),
+                Some(c) => {
+                    cooked.push(c);
+                    raw.push(c);
+                },
+                None => return Err(self.unexpected_eof()),
+            }
+        }
+
+        cooked.shrink_to_fit();
+        raw.shrink_to_fit();
+        Ok(TokenType::String(StringLiteral { cooked, raw, quote }))
+    }
+
+    fn left_brace(&mut self, loc: Location) -> Token {
+        if let Some(depth) = self.brace_stack.last_mut() {
+            *depth += 1;
+        }
+        self.scalar(loc, TokenType::LeftBrace)
+    }
+
+    fn right_brace(&mut self, loc: Location) -> Result<Token, LexError> {
+        match self.brace_stack.last().copied() {
+            Some(0) => {
+                self.brace_stack.pop();
+                self.skip();
+                self.template(loc, false)
+            },
+            Some(_) => {
+                if let Some(depth) = self.brace_stack.last_mut() {
+                    *depth -= 1;
+                }
+                Ok(self.scalar(loc, TokenType::RightBrace))
+            },
+            None => Ok(self.scalar(loc, TokenType::RightBrace)),
+        }
+    }
+
+    // Scans a template literal chunk: raw text up to either the closing
+    // backtick or a `${` that opens an interpolation. `is_head` is true for
+    // the chunk starting right after the opening backtick, false for a
+    // chunk resuming after a `}` closed the previous interpolation;
+    // together with which delimiter we hit, that picks the token kind
+    // (NoSubstitutionTemplate/TemplateHead/TemplateMiddle/TemplateTail).
+    // The backtick or `}` that started this chunk must already be
+    // consumed by the caller.
+    fn template(&mut self, loc: Location, is_head: bool) -> Result<Token, LexError> {
+        let mut s = String::new();
+
+        loop {
+            match self.peek() {
+                Some('`') => {
+                    self.skip();
+                    let typ = if is_head {
+                        TokenType::NoSubstitutionTemplate(s)
+                    } else {
+                        TokenType::TemplateTail(s)
+                    };
+                    return Ok(Token::new(loc, typ));
+                },
+                Some('$') => {
+                    self.skip();
+                    if self.peek() == Some('{') {
+                        self.skip();
+                        self.brace_stack.push(0);
+                        let typ = if is_head {
+                            TokenType::TemplateHead(s)
+                        } else {
+                            TokenType::TemplateMiddle(s)
+                        };
+                        return Ok(Token::new(loc, typ));
+                    }
+                    s.push('$');
+                },
                 Some('\\') => {
+                    self.skip();
                     match self.escape_or_line_continuation() {
                         Ok(escape) => s.push_str(&escape),
-                        Err(x) => return Err(x),
+                        Err(e) => return Err(e),
                     }
                 },
-                Some(c) if is_line_terminator(c) => return Err(self.unexpected_char(c)),
-                Some(c) => s.push(c),
+                Some(c) => {
+                    self.skip();
+                    s.push(c);
+                },
                 None => return Err(self.unexpected_eof()),
             }
         }
+    }
+
+    // matches identifiers and keywords, e.g. foo, $bar, _x, or let
+    fn identifier(&mut self) -> Result<TokenType, LexError> {
+        let mut s = String::new();
+
+        self.identifier_char(&mut s, true)?;
+
+        loop {
+            match self.peek() {
+                Some(c) if is_id_continue(c) => {
+                    s.push(c);
+                    self.skip();
+                },
+                Some('\\') => self.identifier_char(&mut s, false)?,
+                _ => break,
+            }
+        }
 
         s.shrink_to_fit();
-        Ok(TokenType::String(s, quote))
+        Ok(match Keyword::from_str(&s) {
+            Some(kw) => TokenType::Keyword(kw),
+            None => TokenType::Identifier(s),
+        })
+    }
+
+    // Consumes either a plain identifier character or a `\u` escape
+    // (`\uHHHH` or `\u{...}`) and appends the decoded char to `s`. `is_start`
+    // selects the ID_Start vs ID_Continue predicate used to validate the
+    // escaped codepoint, mirroring the two positions a `\u` escape can appear
+    // in an identifier.
+    fn identifier_char(&mut self, s: &mut String, is_start: bool) -> Result<(), LexError> {
+        match self.next_char() {
+            Some('\\') => {
+                match self.next_char() {
+                    Some('u') => {
+                        let c = self.unicode_escape()?;
+                        let valid = if is_start { is_id_start(c) } else { is_id_continue(c) };
+                        if !valid {
+                            return Err(self.unexpected_char(c));
+                        }
+                        s.push(c);
+                        Ok(())
+                    },
+                    Some(c) => Err(self.unexpected_char(c)),
+                    None => Err(self.unexpected_eof()),
+                }
+            },
+            Some(c) => {
+                s.push(c);
+                Ok(())
+            },
+            None => Err(self.unexpected_eof()),
+        }
+    }
+
+    // Decodes a `\u` escape body, either the fixed-width `HHHH` form or the
+    // braced `{...}` form, into the char it denotes.
+    fn unicode_escape(&mut self) -> Result<char, LexError> {
+        let (code, _) = self.unicode_escape_digits()?;
+        char::from_u32(code).ok_or_else(|| self.invalid_code_point(code))
+    }
+
+    // Decodes a `\u` escape body, either the fixed-width `HHHH` form or the
+    // braced `{...}` form, into its codepoint value and the raw hex text
+    // actually consumed (braces included, if present). Shared by identifier
+    // escapes and string escapes.
+    fn unicode_escape_digits(&mut self) -> Result<(u32, String), LexError> {
+        let mut hex = String::new();
+        let mut raw = String::new();
+
+        match self.peek() {
+            Some('{') => {
+                self.skip();
+                raw.push('{');
+                // CodePoint is 1-6 hex digits; anything longer is an error
+                // rather than silently accepting it as a larger value.
+                loop {
+                    match self.peek() {
+                        Some(c) if is_hex_digit(c) => {
+                            if hex.len() == 6 {
+                                return Err(self.unexpected_char(c));
+                            }
+                            hex.push(c);
+                            self.skip();
+                        },
+                        _ => break,
+                    }
+                }
+                raw.push_str(&hex);
+                match self.next_char() {
+                    Some('}') => raw.push('}'),
+                    Some(c) => return Err(self.unexpected_char(c)),
+                    None => return Err(self.unexpected_eof()),
+                }
+            },
+            _ => {
+                for _ in 0..4 {
+                    match self.next_char() {
+                        Some(c) if is_hex_digit(c) => {
+                            hex.push(c);
+                            raw.push(c);
+                        },
+                        Some(c) => return Err(self.unexpected_char(c)),
+                        None => return Err(self.unexpected_eof()),
+                    }
+                }
+            },
+        }
+
+        if hex.is_empty() {
+            return Err(self.unexpected_char('}'));
+        }
+
+        let code = u32::from_str_radix(&hex, 16).map_err(|_| self.unexpected_char('u'))?;
+        Ok((code, raw))
     }
 
-    // matches constructs beginning with a digit, e.g. 0.123 or 10e+42
+    fn invalid_code_point(&self, code: u32) -> LexError {
+        let loc = self.get_location();
+        LexError::InvalidCodePoint(loc, code)
+    }
+
+    // Decodes one string escape sequence (the `\` has already been
+    // consumed), pushing the decoded value onto `cooked` and the exact
+    // source text (including the backslash) onto `raw`.
+    fn escape(&mut self, cooked: &mut String, raw: &mut String) -> Result<(), LexError> {
+        match self.next_char() {
+            // line continuation: contributes nothing to `cooked`
+            Some(c) if is_line_terminator(c) => {
+                raw.push('\\');
+                raw.push(c);
+                if c == '\u{000D}' && self.peek() == Some('\u{000A}') {
+                    self.skip();
+                    raw.push('\u{000A}');
+                }
+                Ok(())
+            },
+            Some('n') => { cooked.push('\u{000A}'); raw.push_str("\\n"); Ok(()) },
+            Some('r') => { cooked.push('\u{000D}'); raw.push_str("\\r"); Ok(()) },
+            Some('t') => { cooked.push('\u{0009}'); raw.push_str("\\t"); Ok(()) },
+            Some('b') => { cooked.push('\u{0008}'); raw.push_str("\\b"); Ok(()) },
+            Some('f') => { cooked.push('\u{000C}'); raw.push_str("\\f"); Ok(()) },
+            Some('v') => { cooked.push('\u{000B}'); raw.push_str("\\v"); Ok(()) },
+            Some('0') => { cooked.push('\u{0000}'); raw.push_str("\\0"); Ok(()) },
+            Some('x') => {
+                raw.push_str("\\x");
+                let mut hex = String::new();
+                for _ in 0..2 {
+                    match self.next_char() {
+                        Some(c) if is_hex_digit(c) => {
+                            hex.push(c);
+                            raw.push(c);
+                        },
+                        Some(c) => return Err(self.unexpected_char(c)),
+                        None => return Err(self.unexpected_eof()),
+                    }
+                }
+                let code = u32::from_str_radix(&hex, 16).expect("validated hex digits");
+                match char::from_u32(code) {
+                    Some(c) => { cooked.push(c); Ok(()) },
+                    None => Err(self.invalid_code_point(code)),
+                }
+            },
+            Some('u') => {
+                raw.push_str("\\u");
+                let (code, hex_raw) = self.unicode_escape_digits()?;
+                raw.push_str(&hex_raw);
+                match char::from_u32(code) {
+                    Some(c) => { cooked.push(c); Ok(()) },
+                    None => Err(self.invalid_code_point(code)),
+                }
+            },
+            Some(c) => {
+                cooked.push(c);
+                raw.push('\\');
+                raw.push(c);
+                Ok(())
+            },
+            None => Err(self.unexpected_eof()),
+        }
+    }
+
+    // matches constructs beginning with a digit, e.g. 0.123, 10e+42, 0xFF,
+    // 0b101, 0o17, 1_000, or 9007199254740993n
     fn digit(&mut self) -> Result<TokenType, LexError> {
         let mut s = String::new();
 
-        // integer part
-        self.push_while(&mut s, is_digit);
+        if self.peek() == Some('0') {
+            self.skip();
+            s.push('0');
+            match self.peek() {
+                Some('x') | Some('X') => return self.radix_literal(s, Radix::Hex, is_hex_digit),
+                Some('o') | Some('O') => return self.radix_literal(s, Radix::Octal, is_octal_digit),
+                Some('b') | Some('B') => return self.radix_literal(s, Radix::Binary, is_binary_digit),
+                Some(c) if is_digit(c) => return self.legacy_octal(s),
+                _ => (),
+            }
+        } else {
+            self.digits_with_separators(&mut s, is_digit)?;
+        }
 
-        // decmal part
-        if let Some('.') = self.peek() {
+        // decimal part
+        let has_fraction = self.peek() == Some('.');
+        if has_fraction {
             match self.decimal() {
                 Ok(d) => s.push_str(&d),
                 Err(e) => return Err(e),
@@ -163,30 +464,37 @@ where I: Iterator<Item = char>,
         }
 
         // exponent part
-        if let Some('e') | Some('E') = self.peek() {
+        let has_exponent = matches!(self.peek(), Some('e') | Some('E'));
+        if has_exponent {
+            s.push(self.peek().expect("exponent marker already peeked"));
             match self.exponent() {
                 Ok(e)  => s.push_str(&e),
                 Err(e) => return Err(e),
             }
         }
 
+        let is_bigint = self.bigint_suffix(&mut s, has_fraction || has_exponent)?;
+        self.check_no_trailing_digit_or_ident()?;
+
         s.shrink_to_fit();
-        Ok(TokenType::Number(s))
+        Ok(TokenType::Number(NumberLiteral {
+            text: s,
+            radix: Radix::Decimal,
+            has_fraction,
+            has_exponent,
+            is_bigint,
+        }))
     }
 
+    // DecimalDigits after the `.` are optional per the DecimalLiteral
+    // grammar, so `1.`, `0.`, and `1.e5` are all valid.
     fn decimal(&mut self) -> Result<String, LexError> {
         self.skip();
-        let mut s = String::new();
-        match self.peek() {
-            Some(c) if is_digit(c) => {
-                self.skip();
-                s.push(c);
-            },
-            Some(c) => return Err(self.unexpected_char(c)),
-            None    => return Err(self.unexpected_eof()),
-        }
+        let mut s = String::from(".");
 
-        self.push_while(&mut s, is_digit);
+        if matches!(self.peek(), Some(c) if is_digit(c)) {
+            self.digits_with_separators(&mut s, is_digit)?;
+        }
 
         s.shrink_to_fit();
         Ok(s)
@@ -203,29 +511,218 @@ where I: Iterator<Item = char>,
             _ => (),
         }
 
-        match self.next_char() {
-            Some(d) if is_digit(d) => s.push(d),
+        match self.peek() {
+            Some(d) if is_digit(d) => (),
             Some(c) => return Err(self.unexpected_char(c)),
             None    => return Err(self.unexpected_eof()),
         }
 
-        self.push_while(&mut s, is_digit);
+        self.digits_with_separators(&mut s, is_digit)?;
 
         s.shrink_to_fit();
         Ok(s)
     }
 
+    // Scans the digit body of a `0x`/`0o`/`0b` literal: `s` already holds the
+    // leading `0`, and the radix marker (`x`/`o`/`b`, either case) is still
+    // unconsumed.
+    fn radix_literal<P>(&mut self, mut s: String, radix: Radix, predicate: P) -> Result<TokenType, LexError>
+    where P: Fn(char) -> bool,
+    {
+        let marker = self.next_char().expect("radix marker already peeked");
+        s.push(marker);
+
+        let mut digits = String::new();
+        self.digits_with_separators(&mut digits, predicate)?;
+        if digits.is_empty() {
+            return match self.peek() {
+                Some(c) => Err(self.unexpected_char(c)),
+                None    => Err(self.unexpected_eof()),
+            };
+        }
+        s.push_str(&digits);
+
+        let is_bigint = self.bigint_suffix(&mut s, false)?;
+        self.check_no_trailing_digit_or_ident()?;
+
+        s.shrink_to_fit();
+        Ok(TokenType::Number(NumberLiteral {
+            text: s,
+            radix,
+            has_fraction: false,
+            has_exponent: false,
+            is_bigint,
+        }))
+    }
+
+    // Scans a legacy octal literal (`0777`) or, if a `8`/`9` digit shows up,
+    // falls back to treating it as a NonOctalDecimalIntegerLiteral (`0789`).
+    // `s` already holds the leading `0`. Unlike a true legacy octal, the
+    // non-octal fallback is just an ordinary decimal integer under the hood,
+    // so it still allows a fraction and exponent.
+    fn legacy_octal(&mut self, mut s: String) -> Result<TokenType, LexError> {
+        let mut digits = String::new();
+        self.push_while(&mut digits, is_digit);
+
+        if digits.chars().all(is_octal_digit) {
+            s.push_str(&digits);
+            self.check_no_trailing_digit_or_ident()?;
+
+            s.shrink_to_fit();
+            return Ok(TokenType::Number(NumberLiteral {
+                text: s,
+                radix: Radix::LegacyOctal,
+                has_fraction: false,
+                has_exponent: false,
+                is_bigint: false,
+            }));
+        }
+
+        s.push_str(&digits);
+
+        let has_fraction = self.peek() == Some('.');
+        if has_fraction {
+            let d = self.decimal()?;
+            s.push_str(&d);
+        }
+
+        let has_exponent = matches!(self.peek(), Some('e') | Some('E'));
+        if has_exponent {
+            s.push(self.peek().expect("exponent marker already peeked"));
+            let e = self.exponent()?;
+            s.push_str(&e);
+        }
+
+        // A NonOctalDecimalIntegerLiteral never takes a BigInt suffix, even
+        // without a fraction or exponent (`0789n` is as invalid as `0789.5n`).
+        let is_bigint = self.bigint_suffix(&mut s, true)?;
+        self.check_no_trailing_digit_or_ident()?;
+
+        s.shrink_to_fit();
+        Ok(TokenType::Number(NumberLiteral {
+            text: s,
+            radix: Radix::Decimal,
+            has_fraction,
+            has_exponent,
+            is_bigint,
+        }))
+    }
+
+    // Accumulates digits matching `predicate` into `s`, allowing a single
+    // `_` separator between digits but rejecting a leading, trailing, or
+    // doubled separator.
+    fn digits_with_separators<P>(&mut self, s: &mut String, predicate: P) -> Result<(), LexError>
+    where P: Fn(char) -> bool,
+    {
+        let mut any_digit = false;
+        let mut last_was_separator = false;
+
+        loop {
+            match self.peek() {
+                Some(c) if predicate(c) => {
+                    self.skip();
+                    s.push(c);
+                    any_digit = true;
+                    last_was_separator = false;
+                },
+                Some('_') => {
+                    if !any_digit || last_was_separator {
+                        return Err(self.unexpected_char('_'));
+                    }
+                    self.skip();
+                    s.push('_');
+                    last_was_separator = true;
+                },
+                _ => break,
+            }
+        }
+
+        if last_was_separator {
+            return Err(self.unexpected_char('_'));
+        }
+
+        Ok(())
+    }
+
+    // Consumes a trailing BigInt `n` suffix, appending it to `s`. Forbidden
+    // after a fraction or exponent.
+    fn bigint_suffix(&mut self, s: &mut String, forbidden: bool) -> Result<bool, LexError> {
+        match self.peek() {
+            Some('n') => {
+                if forbidden {
+                    return Err(self.unexpected_char('n'));
+                }
+                self.skip();
+                s.push('n');
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    // A numeric literal may not be immediately followed by another digit or
+    // an identifier character, e.g. `0b12` or `1n2` or `0x1g`.
+    fn check_no_trailing_digit_or_ident(&mut self) -> Result<(), LexError> {
+        match self.peek() {
+            Some(c) if is_digit(c) || is_id_start(c) => Err(self.unexpected_char(c)),
+            _ => Ok(()),
+        }
+    }
+
     fn slash(&mut self, loc: Location) -> Result<Token, LexError> {
         self.skip();
         match self.peek() {
             Some('/') => self.comment(loc, CommentStyle::SingleLine),
             Some('*') => self.comment(loc, CommentStyle::MultiLine),
+            _ if self.allow_regex => self.regex(loc),
             Some('=') => Ok(self.scalar(loc, TokenType::DivEqual)),
+            // The `/` itself was already consumed above, so (unlike the other
+            // arms here) this token needs no further skip.
             None |
-            Some(_)   => Ok(self.scalar(loc, TokenType::Div)),
+            Some(_)   => Ok(Token::new(loc, TokenType::Div)),
         }
     }
 
+    // Scans a regex literal body up to (but not including) the closing `/`,
+    // then its trailing flags. Only called when `allow_regex` is set, i.e.
+    // the parser has indicated a `/` here begins an expression.
+    fn regex(&mut self, loc: Location) -> Result<Token, LexError> {
+        let mut body = String::new();
+        let mut in_class = false;
+
+        loop {
+            match self.next_char() {
+                Some('\\') => {
+                    body.push('\\');
+                    match self.next_char() {
+                        Some(c) if is_line_terminator(c) => return Err(self.unexpected_char(c)),
+                        Some(c) => body.push(c),
+                        None => return Err(self.unexpected_eof()),
+                    }
+                },
+                Some('[') => {
+                    in_class = true;
+                    body.push('[');
+                },
+                Some(']') if in_class => {
+                    in_class = false;
+                    body.push(']');
+                },
+                Some('/') if !in_class => break,
+                Some(c) if is_line_terminator(c) => return Err(self.unexpected_char(c)),
+                Some(c) => body.push(c),
+                None => return Err(self.unexpected_eof()),
+            }
+        }
+
+        let mut flags = String::new();
+        self.push_while(&mut flags, is_id_continue);
+
+        body.shrink_to_fit();
+        flags.shrink_to_fit();
+        Ok(Token::new(loc, TokenType::Regex(body, flags)))
+    }
+
     fn escape_or_line_continuation(&mut self) -> Result<String, LexError> {
         match self.next_char() {
             // line continuation
@@ -422,7 +919,28 @@ where I: Iterator<Item = char>,
                 }
             },
             Some(c) if is_digit(c) => {
-                self.decimal().map(|x| Token::new(loc, TokenType::Number(x)))
+                let mut s = String::from(".");
+                self.digits_with_separators(&mut s, is_digit)?;
+
+                let has_exponent = matches!(self.peek(), Some('e') | Some('E'));
+                if has_exponent {
+                    s.push(self.peek().expect("exponent marker already peeked"));
+                    match self.exponent() {
+                        Ok(e) => s.push_str(&e),
+                        Err(e) => return Err(e),
+                    }
+                }
+
+                self.check_no_trailing_digit_or_ident()?;
+
+                s.shrink_to_fit();
+                Ok(Token::new(loc, TokenType::Number(NumberLiteral {
+                    text: s,
+                    radix: Radix::Decimal,
+                    has_fraction: true,
+                    has_exponent,
+                    is_bigint: false,
+                })))
             },
             _ => Ok(Token::new(loc, TokenType::Period))
         }
@@ -487,22 +1005,51 @@ fn is_digit(c: char) -> bool {
     }
 }
 
+fn is_hex_digit(c: char) -> bool {
+    c.is_ascii_hexdigit()
+}
+
+fn is_octal_digit(c: char) -> bool {
+    matches!(c, '0'..='7')
+}
+
+fn is_binary_digit(c: char) -> bool {
+    c == '0' || c == '1'
+}
+
+// Approximates the Unicode ID_Start production: `$`, `_`, or an alphabetic
+// codepoint.
+fn is_id_start(c: char) -> bool {
+    c == '$' || c == '_' || c.is_alphabetic()
+}
+
+// Approximates the Unicode ID_Continue production: anything ID_Start allows,
+// plus digits and the zero-width (non-)joiner used to glue some scripts'
+// identifiers together.
+fn is_id_continue(c: char) -> bool {
+    is_id_start(c) || c.is_numeric() || c == '\u{200C}' || c == '\u{200D}'
+}
+
 impl<I> Iterator for Lexer<I>
 where I: Iterator<Item = char>
 {
     type Item = Result<Token, LexError>;
 
     fn next(&mut self) -> Option<Result<Token, LexError>> {
+        let start = self.byte_offset;
         let loc = self.get_location();
-        self.peek().map(|next| {
+        let result = self.peek().map(|next| {
             match next {
                 x if is_ws(x) => Ok(Token::new(loc, self.ws())),
                 x if is_digit(x) => self.digit().map(|x| Token::new(loc, x)),
+                x if is_id_start(x) => self.identifier().map(|x| Token::new(loc, x)),
+                '\\' => self.identifier().map(|x| Token::new(loc, x)),
                 '/'  => self.slash(loc),
                 '\'' => self.string(QuoteStyle::Single).map(|x| Token::new(loc, x)),
                 '"'  => self.string(QuoteStyle::Double).map(|x| Token::new(loc, x)),
-                '{'  => Ok(self.scalar(loc, TokenType::LeftBrace)),
-                '}'  => Ok(self.scalar(loc, TokenType::RightBrace)),
+                '{'  => Ok(self.left_brace(loc)),
+                '}'  => self.right_brace(loc),
+                '`'  => { self.skip(); self.template(loc, true) },
                 '='  => Ok(Token::new(loc, self.equal())),
                 '!'  => Ok(Token::new(loc, self.bang())),
                 '&'  => Ok(Token::new(loc, self.ampersand())),
@@ -526,13 +1073,75 @@ where I: Iterator<Item = char>
                 '?'  => Ok(self.scalar(loc, TokenType::Question)),
                 c    => Err(self.unexpected_char(c)),
             }
-        })
+        });
+
+        let end = self.byte_offset;
+        result.map(|r| r.map(|mut tok| {
+            tok.span = Span { start, end };
+            tok
+        }))
+    }
+}
+
+// Whether `typ` ends an expression, so a `/` immediately after it must be
+// division rather than the start of a regex literal. This is the standard
+// "previous significant token" heuristic engines without a parser's
+// lexer-goal feedback use to drive `allow_regex` on their own. `}` is
+// deliberately excluded: it far more often closes a block (where a regex
+// may follow) than an object literal.
+fn ends_expression(typ: &TokenType) -> bool {
+    match typ {
+        TokenType::Identifier(_) |
+        TokenType::Number(_) |
+        TokenType::String(_) |
+        TokenType::Regex(_, _) |
+        TokenType::NoSubstitutionTemplate(_) |
+        TokenType::TemplateTail(_) |
+        TokenType::RightParen |
+        TokenType::RightBracket |
+        TokenType::Increment |
+        TokenType::Decrement => true,
+        TokenType::Keyword(k) => matches!(k, Keyword::This | Keyword::Super | Keyword::True | Keyword::False | Keyword::Null),
+        _ => false,
     }
 }
 
+/// Drives `lexer` to completion and returns its tokens, with a terminating
+/// `TokenType::EndOfFile` token so consumers don't need to special-case the
+/// end of the stream the way they would with the bare iterator's `None`.
+/// Since nothing here plays the role of a parser, `allow_regex` is driven
+/// from the previous significant (non-whitespace, non-comment) token via
+/// `ends_expression`, so e.g. `a/b` still lexes as division.
+pub fn lex(input: &str) -> Result<Vec<Token>, LexError> {
+    let mut lexer = Lexer::new(input.chars());
+    let mut tokens = Vec::new();
+    let mut prev_ends_expression = false;
+
+    loop {
+        lexer.allow_regex(!prev_ends_expression);
+        let Some(result) = lexer.next() else { break };
+        let token = result?;
+        if !matches!(token.typ, TokenType::WhiteSpace(_) | TokenType::Comment(..)) {
+            prev_ends_expression = ends_expression(&token.typ);
+        }
+        tokens.push(token);
+    }
+
+    let offset = lexer.byte_offset;
+    tokens.push(Token {
+        column: lexer.column,
+        line: lexer.line,
+        typ: TokenType::EndOfFile,
+        span: Span { start: offset, end: offset },
+    });
+
+    Ok(tokens)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::token::Associativity;
 
     #[test]
     fn identifies_whitespace() {
@@ -565,15 +1174,42 @@ mod tests {
     #[test]
     fn identifies_div() {
         let input = "/";
-        let output = first_token(input);
-        assert_eq!(token_text(output), input);
+        let mut lexer = Lexer::new(input.chars());
+        lexer.allow_regex(false);
+        assert_eq!(token_text(lexer.next()), input);
     }
 
     #[test]
     fn identifies_div_equals() {
         let input = "/=";
+        let mut lexer = Lexer::new(input.chars());
+        lexer.allow_regex(false);
+        assert_eq!(token_text(lexer.next()), input);
+    }
+
+    #[test]
+    fn identifies_regex_literals_by_default() {
+        let input = "/ab+c/gi";
         let output = first_token(input);
-        assert_eq!(token_text(output), input);
+        match output {
+            Some(Ok(Token { typ: TokenType::Regex(body, flags), .. })) => {
+                assert_eq!(body, "ab+c");
+                assert_eq!(flags, "gi");
+            },
+            other => panic!("expected a regex literal, got {:?}", other.map(|r| r.map(|t| t.typ))),
+        }
+    }
+
+    #[test]
+    fn regex_character_class_does_not_end_the_literal() {
+        let input = "/[a/b]/";
+        let output = first_token(input);
+        match output {
+            Some(Ok(Token { typ: TokenType::Regex(body, _), .. })) => {
+                assert_eq!(body, "[a/b]");
+            },
+            other => panic!("expected a regex literal, got {:?}", other.map(|r| r.map(|t| t.typ))),
+        }
     }
 
     #[test]
@@ -583,6 +1219,222 @@ mod tests {
         assert_eq!(token_text(output), input);
     }
 
+    #[test]
+    fn identifies_identifiers() {
+        let input = "$foo_bar1";
+        let output = first_token(input);
+        assert_eq!(token_text(output), input);
+    }
+
+    #[test]
+    fn identifies_keywords() {
+        let input = "typeof";
+        let output = first_token(input);
+        assert_eq!(token_text(output), input);
+    }
+
+    #[test]
+    fn decodes_unicode_escapes_in_identifiers() {
+        let input = "\\u0061bc";
+        let output = first_token(input);
+        assert_eq!(token_text(output), "abc");
+    }
+
+    #[test]
+    fn identifies_hex_binary_and_octal_literals() {
+        assert_eq!(first_number("0xFF").text, "0xFF");
+        assert_eq!(first_number("0xFF").radix, Radix::Hex);
+        assert_eq!(first_number("0b101").radix, Radix::Binary);
+        assert_eq!(first_number("0o17").radix, Radix::Octal);
+    }
+
+    #[test]
+    fn rejects_empty_radix_literal() {
+        let input = "0x";
+        let mut lexer = Lexer::new(input.chars());
+        assert!(lexer.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn distinguishes_legacy_octal_from_explicit_octal() {
+        assert_eq!(first_number("0777").radix, Radix::LegacyOctal);
+        assert_eq!(first_number("0789").radix, Radix::Decimal);
+    }
+
+    #[test]
+    fn non_octal_decimal_integer_still_parses_fraction() {
+        let n = first_number("0789.5");
+        assert_eq!(n.text, "0789.5");
+        assert_eq!(n.radix, Radix::Decimal);
+        assert!(n.has_fraction);
+    }
+
+    #[test]
+    fn rejects_bigint_suffix_on_non_octal_decimal_integer() {
+        let mut lexer = Lexer::new("0789n".chars());
+        assert!(lexer.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn allows_numeric_separators() {
+        let n = first_number("1_000_000");
+        assert_eq!(n.text, "1_000_000");
+    }
+
+    #[test]
+    fn rejects_leading_trailing_and_doubled_separators() {
+        for input in &["1._5", "1_", "1__2"] {
+            let mut lexer = Lexer::new(input.chars());
+            assert!(lexer.next().unwrap().is_err(), "expected {} to be rejected", input);
+        }
+    }
+
+    #[test]
+    fn rejects_leading_separator_in_radix_literals() {
+        let mut lexer = Lexer::new("0x_1".chars());
+        assert!(lexer.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn identifies_bigint_literals() {
+        let n = first_number("9007199254740993n");
+        assert!(n.is_bigint);
+        assert_eq!(n.text, "9007199254740993n");
+    }
+
+    #[test]
+    fn allows_decimal_literals_with_no_fraction_digits() {
+        let n = first_number("1.");
+        assert!(n.has_fraction);
+        assert_eq!(n.text, "1.");
+    }
+
+    #[test]
+    fn identifies_no_substitution_templates() {
+        let input = "`hello world`";
+        let output = first_token(input);
+        match output {
+            Some(Ok(Token { typ: TokenType::NoSubstitutionTemplate(s), .. })) => {
+                assert_eq!(s, "hello world");
+            },
+            other => panic!("expected a template literal, got {:?}", other.map(|r| r.map(|t| t.typ))),
+        }
+    }
+
+    #[test]
+    fn splits_template_interpolations_into_head_and_tail() {
+        let input = "`a${1}b`";
+        let mut lexer = Lexer::new(input.chars());
+
+        let head = lexer.next().unwrap().unwrap();
+        assert!(matches!(head.typ, TokenType::TemplateHead(ref s) if s == "a"));
+
+        let num = lexer.next().unwrap().unwrap();
+        assert!(matches!(num.typ, TokenType::Number(_)));
+
+        let tail = lexer.next().unwrap().unwrap();
+        assert!(matches!(tail.typ, TokenType::TemplateTail(ref s) if s == "b"));
+    }
+
+    #[test]
+    fn object_literal_brace_inside_interpolation_does_not_resume_template() {
+        let input = "`a${ {x: 1}.x }b`";
+        let mut lexer = Lexer::new(input.chars());
+
+        let head = lexer.next().unwrap().unwrap();
+        assert!(matches!(head.typ, TokenType::TemplateHead(_)));
+
+        let mut saw_right_brace = false;
+        loop {
+            match lexer.next().unwrap().unwrap().typ {
+                TokenType::RightBrace => saw_right_brace = true,
+                TokenType::TemplateTail(ref s) => {
+                    assert_eq!(s, "b");
+                    break;
+                },
+                _ => (),
+            }
+        }
+        assert!(saw_right_brace);
+    }
+
+    #[test]
+    fn decodes_simple_escapes_in_strings() {
+        let lit = first_string(r#""a\nb""#);
+        assert_eq!(lit.cooked, "a\nb");
+        assert_eq!(lit.raw, r"a\nb");
+    }
+
+    #[test]
+    fn decodes_unicode_escapes_in_strings() {
+        let lit = first_string(r#""A\u{1F600}""#);
+        assert_eq!(lit.cooked, "A\u{1F600}");
+    }
+
+    #[test]
+    fn decodes_hex_escapes_in_strings() {
+        let lit = first_string(r#""\x41""#);
+        assert_eq!(lit.cooked, "A");
+    }
+
+    #[test]
+    fn rejects_out_of_range_unicode_escapes() {
+        let input = r#""\u{110000}""#;
+        let mut lexer = Lexer::new(input.chars());
+        assert!(matches!(lexer.next(), Some(Err(LexError::InvalidCodePoint(_, _)))));
+    }
+
+    #[test]
+    fn rejects_overlong_braced_unicode_escapes() {
+        let input = r#""\u{0000001}""#;
+        let mut lexer = Lexer::new(input.chars());
+        assert!(lexer.next().unwrap().is_err());
+    }
+
+    fn first_string(input: &str) -> StringLiteral {
+        let mut lexer = Lexer::new(input.chars());
+        match lexer.next() {
+            Some(Ok(Token { typ: TokenType::String(s), .. })) => s,
+            other => panic!("expected a string literal, got {:?}", other.map(|r| r.map(|t| t.typ))),
+        }
+    }
+
+    #[test]
+    fn lex_appends_an_eof_token() {
+        let tokens = lex("1").unwrap();
+        assert!(matches!(tokens.last().unwrap().typ, TokenType::EndOfFile));
+    }
+
+    #[test]
+    fn lex_reports_byte_offset_spans() {
+        let tokens = lex("1 ab").unwrap();
+        assert_eq!(tokens[0].span, Span { start: 0, end: 1 });
+        assert_eq!(tokens[1].span, Span { start: 1, end: 2 });
+        assert_eq!(tokens[2].span, Span { start: 2, end: 4 });
+    }
+
+    #[test]
+    fn lex_treats_slash_after_an_operand_as_division() {
+        let tokens = lex("a/b").unwrap();
+        assert!(matches!(tokens[0].typ, TokenType::Identifier(_)));
+        assert!(matches!(tokens[1].typ, TokenType::Div));
+        assert!(matches!(tokens[2].typ, TokenType::Identifier(_)));
+    }
+
+    #[test]
+    fn lex_still_allows_regex_literals_in_expression_position() {
+        let tokens = lex("x = /ab+c/g").unwrap();
+        assert!(matches!(tokens[4].typ, TokenType::Regex(_, _)));
+    }
+
+    fn first_number(input: &str) -> NumberLiteral {
+        let mut lexer = Lexer::new(input.chars());
+        match lexer.next() {
+            Some(Ok(Token { typ: TokenType::Number(n), .. })) => n,
+            other => panic!("expected a number literal, got {:?}", other.map(|r| r.map(|t| t.typ))),
+        }
+    }
+
     fn first_token(input: &str) -> Option<Result<Token, LexError>> {
         let mut lexer = Lexer::new(input.chars());
         lexer.next()
@@ -595,4 +1447,40 @@ mod tests {
             None => panic!("Didn't get a token from the lexer")
         }
     }
+
+    #[test]
+    fn orders_binary_operator_precedence() {
+        assert!(TokenType::LogicalOr.precedence() < TokenType::LogicalAnd.precedence());
+        assert!(TokenType::LogicalAnd.precedence() < TokenType::BinaryOr.precedence());
+        assert!(TokenType::BinaryOr.precedence() < TokenType::BinaryXor.precedence());
+        assert!(TokenType::BinaryXor.precedence() < TokenType::BinaryAnd.precedence());
+        assert!(TokenType::BinaryAnd.precedence() < TokenType::TripleEquals.precedence());
+        assert!(TokenType::TripleEquals.precedence() < TokenType::LessThan.precedence());
+        assert!(TokenType::LessThan.precedence() < TokenType::LeftShift.precedence());
+        assert!(TokenType::LeftShift.precedence() < TokenType::Plus.precedence());
+        assert!(TokenType::Plus.precedence() < TokenType::Times.precedence());
+        assert!(TokenType::Times.precedence() < TokenType::Power.precedence());
+    }
+
+    #[test]
+    fn non_operator_and_non_binary_tokens_have_no_precedence() {
+        assert_eq!(TokenType::Bang.precedence(), None);
+        assert_eq!(TokenType::Comma.precedence(), None);
+        assert_eq!(TokenType::EndOfFile.precedence(), None);
+    }
+
+    #[test]
+    fn power_and_assignment_operators_are_right_associative() {
+        assert_eq!(TokenType::Power.associativity(), Some(Associativity::Right));
+        assert_eq!(TokenType::Equals.associativity(), Some(Associativity::Right));
+        assert_eq!(TokenType::PlusEquals.associativity(), Some(Associativity::Right));
+        assert_eq!(TokenType::Plus.associativity(), Some(Associativity::Left));
+    }
+
+    #[test]
+    fn displays_operators_as_their_source_lexeme() {
+        assert_eq!(TokenType::Plus.to_string(), "+");
+        assert_eq!(TokenType::TripleRightShiftEquals.to_string(), ">>>=");
+        assert_eq!(TokenType::Arrow.to_string(), "=>");
+    }
 }
@@ -1,7 +1,8 @@
 use std::fmt;
 use super::location::Location;
+use super::keyword::Keyword;
 
-#[derive(PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum QuoteStyle {
     Single,
     Double,
@@ -16,24 +17,58 @@ impl fmt::Display for QuoteStyle {
     }
 }
 
+#[derive(Debug)]
 pub enum CommentStyle {
     SingleLine,
     MultiLine,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Radix {
+    Binary,
+    Octal,
+    LegacyOctal,
+    Decimal,
+    Hex,
+}
+
+/// A numeric literal's source text plus the structure a parser would
+/// otherwise have to re-scan: which radix it was written in, whether it had
+/// a fraction or exponent part, and whether it carries the `n` BigInt
+/// suffix.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NumberLiteral {
+    pub text: String,
+    pub radix: Radix,
+    pub has_fraction: bool,
+    pub has_exponent: bool,
+    pub is_bigint: bool,
+}
+
+/// A byte-offset range into the original source, `[start, end)`, so tools
+/// that index into the source (error underlining, source maps) can slice
+/// the exact text of a token without re-deriving offsets from line/column.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A string literal's decoded (`cooked`) value alongside its exact source
+/// text (`raw`), so an evaluator can use the former while a pretty-printer
+/// round-trips the latter.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StringLiteral {
+    pub cooked: String,
+    pub raw: String,
+    pub quote: QuoteStyle,
+}
+
 pub struct Token {
     pub column: u32,
     pub line: u32,
     pub typ: TokenType,
-}
-
-pub enum TokenType {
-    Comment(String, CommentStyle),
-    Div,
-    DivEqual,
-    RightBrace,
-    String(String, QuoteStyle),
-    WhiteSpace(String),
+    pub span: Span,
 }
 
 impl Token {
@@ -42,6 +77,151 @@ impl Token {
             column: loc.column,
             line: loc.line,
             typ,
+            span: Span::default(),
+        }
+    }
+}
+
+/// Whether a binary operator groups with operators of its own precedence to
+/// its left (`a - b - c == (a - b) - c`) or to its right (`a ** b ** c == a
+/// ** (b ** c)`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+/// Defines `TokenType` from its non-operator (`data`) variants and its
+/// operator variants, deriving the metadata a parser needs about the
+/// latter: `precedence`/`associativity` for the operators annotated with
+/// them, and a `Display` impl rendering every operator back to its source
+/// lexeme. Keeping the lexeme, precedence and associativity next to each
+/// variant's declaration is what a hand-maintained `impl Display for
+/// TokenType` would otherwise drift away from.
+macro_rules! token_kind {
+    (
+        data {
+            $( $dvariant:ident $( ( $( $dty:ty ),+ ) )? ),* $(,)?
+        }
+        operators {
+            $( $ovariant:ident => $lexeme:literal $(, $prec:literal, $assoc:ident)? ),* $(,)?
         }
+    ) => {
+        #[derive(Debug)]
+        pub enum TokenType {
+            $( $dvariant $( ( $( $dty ),+ ) )?, )*
+            $( $ovariant, )*
+        }
+
+        impl TokenType {
+            /// Binary-operator binding power: higher binds tighter. `None`
+            /// for every token that isn't a binary or assignment operator.
+            pub fn precedence(&self) -> Option<u8> {
+                match self {
+                    $( $( TokenType::$ovariant => Some($prec), )? )*
+                    _ => None,
+                }
+            }
+
+            /// `Some` only where `precedence` is also `Some`.
+            pub fn associativity(&self) -> Option<Associativity> {
+                match self {
+                    $( $( TokenType::$ovariant => Some(Associativity::$assoc), )? )*
+                    _ => None,
+                }
+            }
+        }
+
+        impl fmt::Display for TokenType {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    TokenType::Comment(s, CommentStyle::SingleLine) => write!(f, "//{}", s),
+                    TokenType::Comment(s, CommentStyle::MultiLine) => write!(f, "/*{}*/", s),
+                    TokenType::EndOfFile => Ok(()),
+                    TokenType::Identifier(s) => write!(f, "{}", s),
+                    TokenType::Keyword(k) => write!(f, "{}", k),
+                    TokenType::Number(n) => write!(f, "{}", n.text),
+                    TokenType::Regex(body, flags) => write!(f, "/{}/{}", body, flags),
+                    TokenType::String(lit) => write!(f, "{}{}{}", lit.quote, lit.raw, lit.quote),
+                    TokenType::NoSubstitutionTemplate(s) => write!(f, "`{}`", s),
+                    TokenType::TemplateHead(s) => write!(f, "`{}${{", s),
+                    TokenType::TemplateMiddle(s) => write!(f, "}}{}${{", s),
+                    TokenType::TemplateTail(s) => write!(f, "}}{}`", s),
+                    TokenType::WhiteSpace(s) => write!(f, "{}", s),
+                    $( TokenType::$ovariant => write!(f, "{}", $lexeme), )*
+                }
+            }
+        }
+    };
+}
+
+token_kind! {
+    data {
+        Comment(String, CommentStyle),
+        EndOfFile,
+        Identifier(String),
+        Keyword(Keyword),
+        Number(NumberLiteral),
+        Regex(String, String),
+        String(StringLiteral),
+        NoSubstitutionTemplate(String),
+        TemplateHead(String),
+        TemplateMiddle(String),
+        TemplateTail(String),
+        WhiteSpace(String),
+    }
+    operators {
+        Arrow => "=>",
+        Bang => "!",
+        BinaryAnd => "&", 6, Left,
+        BinaryAndEquals => "&=", 1, Right,
+        BinaryOr => "|", 4, Left,
+        BinaryOrEquals => "|=", 1, Right,
+        BinaryXor => "^", 5, Left,
+        BinaryXorEquals => "^=", 1, Right,
+        Colon => ":",
+        Comma => ",",
+        Decrement => "--",
+        Div => "/", 11, Left,
+        DivEqual => "/=", 1, Right,
+        DoubleEquals => "==", 7, Left,
+        Ellipsis => "...",
+        Equals => "=", 1, Right,
+        GreaterThan => ">", 8, Left,
+        GreaterThanEqualTo => ">=", 8, Left,
+        Increment => "++",
+        LeftBrace => "{",
+        LeftBracket => "[",
+        LeftParen => "(",
+        LeftShift => "<<", 9, Left,
+        LeftShiftEquals => "<<=", 1, Right,
+        LessThan => "<", 8, Left,
+        LessThanEqualTo => "<=", 8, Left,
+        LogicalAnd => "&&", 3, Left,
+        LogicalOr => "||", 2, Left,
+        Minus => "-", 10, Left,
+        MinusEquals => "-=", 1, Right,
+        NotEquals => "!=", 7, Left,
+        NotTripleEquals => "!==", 7, Left,
+        Percent => "%", 11, Left,
+        PercentEquals => "%=", 1, Right,
+        Period => ".",
+        Plus => "+", 10, Left,
+        PlusEquals => "+=", 1, Right,
+        Power => "**", 12, Right,
+        PowerEquals => "**=", 1, Right,
+        Question => "?",
+        RightBrace => "}",
+        RightBracket => "]",
+        RightParen => ")",
+        RightShift => ">>", 9, Left,
+        RightShiftEquals => ">>=", 1, Right,
+        Semicolon => ";",
+        Tilde => "~",
+        Times => "*", 11, Left,
+        TimesEquals => "*=", 1, Right,
+        TripleEquals => "===", 7, Left,
+        TripleRightShift => ">>>", 9, Left,
+        TripleRightShiftEquals => ">>>=", 1, Right,
     }
 }